@@ -1,11 +1,20 @@
-use std::{
-    io::{self, Write},
+use core::{
+    mem::MaybeUninit,
     ops::{Deref, DerefMut},
     sync::atomic::{AtomicU32, Ordering::*},
 };
 
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
+#[cfg(feature = "std")]
+use std::io::{self, IoSlice, Read, Write};
+
+use bytes::{buf::UninitSlice, Buf, BufMut};
+
 use crate::{
-    header::{HeaderMut, HeaderRo},
+    error::Error,
+    header::{Header, HeaderMut, HeaderRo},
     Ringal, MAXALLOCBYTES, MINALLOCBYTES, U32SIZE,
 };
 
@@ -14,25 +23,25 @@ pub struct BufferWriter<'a> {
     pub(crate) header: HeaderMut,
     pub(crate) initialized: usize,
     pub(crate) capacity: usize,
-    pub(crate) ringo: &'a mut Ringal,
+    pub(crate) ringo: &'a Ringal,
 }
 
 impl<'a> BufferWriter<'a> {
     pub fn finish(self) -> BufferMut {
-        let inner = unsafe { std::slice::from_raw_parts_mut(self.inner, self.initialized) };
+        let inner = unsafe { core::slice::from_raw_parts_mut(self.inner, self.initialized) };
         self.header.set();
+        let len = inner.len();
         BufferMut {
             header: self.header.into(),
             inner,
+            len,
         }
     }
-}
 
-impl<'a> Write for BufferWriter<'a> {
-    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        if self.initialized == MAXALLOCBYTES {
-            io::Error::other("max allocation size reached");
-        }
+    /// Copies `buf` into the ring's uninitialized tail, extending capacity
+    /// first if it doesn't fit yet. The `core`-only implementation behind
+    /// the `std`-gated `Write` impl, so it keeps working without `std`.
+    pub fn write_bytes(&mut self, buf: &[u8]) -> Result<usize, Error> {
         let len = buf.len().min(MAXALLOCBYTES - self.initialized);
 
         let required = self.initialized + len;
@@ -50,37 +59,173 @@ impl<'a> Write for BufferWriter<'a> {
         Ok(len)
     }
 
-    fn flush(&mut self) -> std::io::Result<()> {
+    /// Reads up to `n` bytes straight from `src` into the ring's
+    /// uninitialized tail, extending capacity first if `n` doesn't fit yet.
+    #[cfg(feature = "std")]
+    pub fn read_from<R: Read>(&mut self, src: &mut R, n: usize) -> io::Result<usize> {
+        let n = n.min(MAXALLOCBYTES - self.initialized);
+        let required = self.initialized + n;
+        if required > self.capacity {
+            let extra = (required - self.capacity).max(MINALLOCBYTES + U32SIZE);
+            self.ringo.extend(&self.header, extra)?;
+            self.capacity = self.header.capacity() as usize * U32SIZE;
+        }
+        let dst = unsafe { core::slice::from_raw_parts_mut(self.inner.add(self.initialized), n) };
+        let read = src.read(dst)?;
+        self.initialized += read;
+        Ok(read)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> Write for BufferWriter<'a> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Ok(self.write_bytes(buf)?)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
         Ok(())
     }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        let total: usize = bufs.iter().map(|buf| buf.len()).sum();
+        let len = total.min(MAXALLOCBYTES - self.initialized);
+
+        let required = self.initialized + len;
+        if required > self.capacity {
+            let extra = (required - self.capacity).max(MINALLOCBYTES + U32SIZE);
+            self.ringo.extend(&self.header, extra)?;
+            self.capacity = self.header.capacity() as usize * U32SIZE;
+        }
+
+        let mut written = 0;
+        for buf in bufs {
+            if written == len {
+                break;
+            }
+            let take = buf.len().min(len - written);
+            unsafe {
+                self.inner
+                    .add(self.initialized + written)
+                    .copy_from_nonoverlapping(buf.as_ptr(), take)
+            };
+            written += take;
+        }
+        self.initialized += written;
+        Ok(written)
+    }
 }
 
+#[derive(Debug)]
 pub struct BufferMut {
     pub(crate) header: HeaderRo,
     pub(crate) inner: &'static mut [u8],
+    pub(crate) len: usize,
+}
+
+/// Initialized length versus the rounded-up ring capacity backing a
+/// [`BufferMut`], mirroring the target/actual split TCP send and receive
+/// buffers expose.
+pub struct BufferLimits {
+    pub len: usize,
+    pub capacity: usize,
+}
+
+impl BufferMut {
+    /// Reports [`BufferLimits`] for this frame.
+    pub fn limits(&self) -> BufferLimits {
+        BufferLimits {
+            len: self.len,
+            capacity: self.header.capacity() as usize * U32SIZE,
+        }
+    }
+
+    /// Grows or shrinks the frame in place to `new_len`, treating a single
+    /// ring slot as a growable streaming window rather than a fixed size
+    /// committed to at `writer()`/`fixed()` time. Growing past the current
+    /// ring capacity extends the backing allocation first. Any growth
+    /// zero-fills the grown tail; shrinking just narrows the `inner` view.
+    pub fn resize(&mut self, ringal: &Ringal, new_len: usize) -> Result<(), Error> {
+        let capacity = self.header.capacity() as usize * U32SIZE;
+        if new_len > capacity {
+            let extra = (new_len - capacity).max(MINALLOCBYTES + U32SIZE);
+            let header = Header(self.header.0.cast_mut());
+            ringal.extend(&header, extra)?;
+            // `extend` rewrites the header's size, which clears its busy bit
+            header.set();
+        }
+
+        let old_len = self.len;
+        let ptr = self.inner.as_mut_ptr();
+        self.inner = unsafe { core::slice::from_raw_parts_mut(ptr, new_len) };
+        if new_len > old_len {
+            self.inner[old_len..].fill(0);
+        }
+        self.len = new_len;
+        Ok(())
+    }
+
+    /// The unused tail of the backing ring slot, beyond what's currently
+    /// initialized. `resize` zero-fills any tail it grows into, so it does
+    /// not commit bytes written here.
+    pub fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<u8>] {
+        let capacity = self.header.capacity() as usize * U32SIZE;
+        let ptr = self.inner.as_mut_ptr();
+        unsafe {
+            core::slice::from_raw_parts_mut(
+                ptr.add(self.len).cast::<MaybeUninit<u8>>(),
+                capacity - self.len,
+            )
+        }
+    }
 }
 
+#[cfg(feature = "alloc")]
 impl BufferMut {
     pub fn freeze(self) -> Buffer {
         let rc = Box::leak(Box::new(AtomicU32::new(1)));
-        let inner = unsafe { std::slice::from_raw_parts(self.inner.as_ptr(), self.inner.len()) };
+        let inner = unsafe { core::slice::from_raw_parts(self.inner.as_ptr(), self.inner.len()) };
         let ro = Buffer {
             inner,
             rc,
             header: self.header.clone(),
+            cursor: 0,
         };
         // don't run Drop on BufferMut, Buffer is now responsible for cleanup
-        std::mem::forget(self);
+        core::mem::forget(self);
         ro
     }
 }
 
+/// # Safety
+/// `chunk_mut` only ever exposes the spare tail of `inner`, which is already
+/// backed by live memory (see the safety note on `Ringal::fixed`), and
+/// `advance_mut` keeps `len` within `inner.len()`.
+unsafe impl BufMut for BufferMut {
+    fn remaining_mut(&self) -> usize {
+        self.inner.len() - self.len
+    }
+
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        assert!(cnt <= self.remaining_mut(), "BufferMut advanced past its end");
+        self.len += cnt;
+    }
+
+    fn chunk_mut(&mut self) -> &mut UninitSlice {
+        (&mut self.inner[self.len..]).into()
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
 pub struct Buffer {
     header: HeaderRo,
     inner: &'static [u8],
     rc: &'static AtomicU32,
+    cursor: usize,
 }
 
+#[cfg(feature = "alloc")]
 impl Deref for Buffer {
     type Target = [u8];
     fn deref(&self) -> &Self::Target {
@@ -88,6 +233,7 @@ impl Deref for Buffer {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Clone for Buffer {
     fn clone(&self) -> Self {
         self.rc.fetch_add(1, Release);
@@ -95,10 +241,124 @@ impl Clone for Buffer {
             inner: self.inner,
             rc: self.rc,
             header: self.header.clone(),
+            cursor: self.cursor,
         }
     }
 }
 
+#[cfg(feature = "alloc")]
+impl Buf for Buffer {
+    fn remaining(&self) -> usize {
+        self.inner.len() - self.cursor
+    }
+
+    fn chunk(&self) -> &[u8] {
+        &self.inner[self.cursor..]
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        assert!(cnt <= self.remaining(), "Buffer advanced past its end");
+        self.cursor += cnt;
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Buffer {
+    /// Splits the buffer into two at the given index, zero-copy: both halves
+    /// keep pointing into the same ring slot and the slot is only released
+    /// once the last split view drops.
+    ///
+    /// Afterwards `self` holds `[at, len)`, and the returned `Buffer` holds
+    /// `[0, at)`.
+    pub fn split_to(&mut self, at: usize) -> Buffer {
+        assert!(at <= self.inner.len(), "split index out of bounds");
+        self.rc.fetch_add(1, Release);
+        let (front, back) = self.inner.split_at(at);
+        let split = Buffer {
+            header: self.header.clone(),
+            inner: front,
+            rc: self.rc,
+            cursor: 0,
+        };
+        self.inner = back;
+        self.cursor = self.cursor.saturating_sub(at);
+        split
+    }
+
+    /// Splits the buffer into two at the given index, zero-copy: both halves
+    /// keep pointing into the same ring slot and the slot is only released
+    /// once the last split view drops.
+    ///
+    /// Afterwards `self` holds `[0, at)`, and the returned `Buffer` holds
+    /// `[at, len)`.
+    pub fn split_off(&mut self, at: usize) -> Buffer {
+        assert!(at <= self.inner.len(), "split index out of bounds");
+        self.rc.fetch_add(1, Release);
+        let (front, back) = self.inner.split_at(at);
+        let split = Buffer {
+            header: self.header.clone(),
+            inner: back,
+            rc: self.rc,
+            cursor: 0,
+        };
+        self.inner = front;
+        self.cursor = self.cursor.min(at);
+        split
+    }
+
+    /// Reclaims write access when this is the only reference to the
+    /// backing slot, without copying. Returns `self` unchanged otherwise.
+    pub fn try_into_mut(self) -> Result<BufferMut, Buffer> {
+        if self.rc.load(Acquire) != 1 {
+            return Err(self);
+        }
+        let len = self.inner.len();
+        let inner = unsafe { core::slice::from_raw_parts_mut(self.inner.as_ptr() as *mut u8, len) };
+        let header = self.header.clone();
+        // SAFETY: rc == 1, we are the sole reference holder, so reclaiming
+        // the rc allocation here is the same thing `Buffer`'s own `Drop`
+        // does on the last release
+        let _ = unsafe { Box::from_raw(self.rc.as_ptr() as *mut AtomicU32) };
+        core::mem::forget(self);
+        Ok(BufferMut { header, inner, len })
+    }
+
+    /// The `Arc::make_mut` pattern adapted to the ring allocator: promotes
+    /// in place when this is the sole reference, otherwise copies into a
+    /// freshly allocated frame and leaves this shared copy alive.
+    pub fn make_mut(&mut self, ringal: &Ringal) -> BufferMut {
+        if self.rc.load(Acquire) == 1 {
+            let len = self.inner.len();
+            let inner = unsafe {
+                core::slice::from_raw_parts_mut(self.inner.as_ptr() as *mut u8, len)
+            };
+            let header = self.header.clone();
+            // SAFETY: rc == 1, we are the sole reference holder, so the rc
+            // allocation can be reclaimed right here rather than waiting
+            // for `self` to drop
+            let _ = unsafe { Box::from_raw(self.rc.as_ptr() as *mut AtomicU32) };
+            // The real slot now belongs solely to the returned `BufferMut`;
+            // detach `self` onto an empty, freestanding placeholder so its
+            // `Drop` has nothing left of the original allocation to touch.
+            // A null header needs no allocation of its own; `Drop` skips
+            // `unset()` when it sees one.
+            self.header = Header(core::ptr::null());
+            self.inner = &[];
+            self.rc = Box::leak(Box::new(AtomicU32::new(1)));
+            return BufferMut { header, inner, len };
+        }
+
+        let mut writer = ringal
+            .writer(self.inner.len())
+            .expect("ring buffer has no room to copy this buffer into a fresh frame");
+        writer
+            .write_bytes(self)
+            .expect("writing into a freshly allocated frame cannot fail");
+        writer.finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
 impl Drop for Buffer {
     fn drop(&mut self) {
         let mut count = self.rc.load(Acquire);
@@ -112,7 +372,10 @@ impl Drop for Buffer {
                 return;
             }
         }
-        self.header.unset();
+        // `make_mut`'s detached placeholder has no real header backing it.
+        if !self.header.0.is_null() {
+            self.header.unset();
+        }
         // SAFETY: checked above that we are the last reference holder,
         // which makes it safe to reclaim the storage for AtomicU32
         let _ = unsafe { Box::from_raw(self.rc.as_ptr() as *mut AtomicU32) };
@@ -139,4 +402,5 @@ impl DerefMut for BufferMut {
 }
 
 unsafe impl Send for BufferMut {}
+#[cfg(feature = "alloc")]
 unsafe impl Send for Buffer {}
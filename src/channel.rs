@@ -0,0 +1,180 @@
+use core::{
+    cell::UnsafeCell,
+    mem::MaybeUninit,
+    sync::atomic::{AtomicUsize, Ordering::*},
+};
+
+use alloc::{boxed::Box, sync::Arc};
+
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+
+use crate::{Buffer, BufferWriter, Ringal, MINALLOCBYTES};
+
+struct Slot {
+    buffer: UnsafeCell<MaybeUninit<Buffer>>,
+}
+
+struct Queue {
+    slots: Box<[Slot]>,
+    head: AtomicUsize,
+    tail: AtomicUsize,
+}
+
+// SAFETY: `Queue` is only ever shared between exactly one producer and one
+// consumer; each slot is written by the producer and read by the consumer
+// exactly once between the `head`/`tail` handoffs below.
+unsafe impl Sync for Queue {}
+
+impl Queue {
+    fn new(depth: usize) -> Self {
+        let slots = (0..depth.max(1))
+            .map(|_| Slot {
+                buffer: UnsafeCell::new(MaybeUninit::uninit()),
+            })
+            .collect();
+        Self {
+            slots,
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+        }
+    }
+
+    fn depth(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// # Safety
+    /// must only ever be called from the single producer
+    unsafe fn push(&self, buffer: Buffer) -> Result<(), Buffer> {
+        let head = self.head.load(Relaxed);
+        let tail = self.tail.load(Acquire);
+        if head - tail == self.depth() {
+            return Err(buffer);
+        }
+        let slot = &self.slots[head % self.depth()];
+        unsafe { (*slot.buffer.get()).write(buffer) };
+        self.head.store(head + 1, Release);
+        Ok(())
+    }
+
+    /// # Safety
+    /// must only ever be called from the single consumer
+    unsafe fn pop(&self) -> Option<Buffer> {
+        let tail = self.tail.load(Relaxed);
+        let head = self.head.load(Acquire);
+        if tail == head {
+            return None;
+        }
+        let slot = &self.slots[tail % self.depth()];
+        let buffer = unsafe { (*slot.buffer.get()).assume_init_read() };
+        self.tail.store(tail + 1, Release);
+        Some(buffer)
+    }
+}
+
+impl Drop for Queue {
+    fn drop(&mut self) {
+        // Any frames still sitting between `tail` and `head` were never
+        // popped, so their `Buffer`s never ran their destructor — drop
+        // them here or their ring headers (and rc allocations) leak.
+        let tail = *self.tail.get_mut();
+        let head = *self.head.get_mut();
+        for i in tail..head {
+            let slot = &self.slots[i % self.depth()];
+            unsafe { (*slot.buffer.get()).assume_init_drop() };
+        }
+    }
+}
+
+/// Producer half of a framed byte channel. Owns the backing `Ringal` and
+/// hands out one [`FrameWriter`] per message.
+pub struct Sender {
+    ringal: Ringal,
+    queue: Arc<Queue>,
+}
+
+/// Consumer half of a framed byte channel. Pops frames in FIFO order;
+/// dropping a received `Buffer` releases its ring slot back to the
+/// `Sender`.
+pub struct Receiver {
+    queue: Arc<Queue>,
+}
+
+// SAFETY: a `Sender`/`Receiver` pair hands off `Ringal`'s raw pointers and
+// the shared queue across exactly the two threads that own them, never
+// concurrently from more than one producer or consumer.
+unsafe impl Send for Sender {}
+unsafe impl Send for Receiver {}
+
+/// Creates an SPSC framed byte channel backed by a `Ringal` of `capacity`
+/// bytes. The queue's depth scales with how many minimally sized frames
+/// could be in flight at once, so the `Sender`'s allocations and the
+/// queue's depth apply back-pressure in tandem.
+pub fn channel(capacity: usize) -> (Sender, Receiver) {
+    let depth = capacity / MINALLOCBYTES;
+    let queue = Arc::new(Queue::new(depth));
+    let ringal = Ringal::new(capacity);
+    (
+        Sender {
+            ringal,
+            queue: queue.clone(),
+        },
+        Receiver { queue },
+    )
+}
+
+impl Sender {
+    /// Starts framing a new message. Returns `None` if the ring has no room
+    /// for at least `min` bytes right now.
+    pub fn writer(&mut self, min: usize) -> Option<FrameWriter<'_>> {
+        let inner = self.ringal.writer(min)?;
+        Some(FrameWriter {
+            inner,
+            queue: &self.queue,
+        })
+    }
+}
+
+impl Receiver {
+    /// Pops the oldest pending frame, if any.
+    pub fn recv(&mut self) -> Option<Buffer> {
+        // SAFETY: `Receiver` is the single consumer of `queue`.
+        unsafe { self.queue.pop() }
+    }
+}
+
+/// Wraps a [`BufferWriter`] so that finishing it freezes the frame and
+/// publishes it straight into the channel's queue.
+pub struct FrameWriter<'a> {
+    inner: BufferWriter<'a>,
+    queue: &'a Queue,
+}
+
+impl<'a> FrameWriter<'a> {
+    /// Copies `buf` into the frame. The `core`-only counterpart of the
+    /// `std`-gated `Write` impl below.
+    pub fn write_bytes(&mut self, buf: &[u8]) -> Result<usize, crate::Error> {
+        self.inner.write_bytes(buf)
+    }
+
+    /// Freezes the frame and publishes it to the `Receiver`. Returns the
+    /// frozen `Buffer` back if the queue is still full, i.e. the consumer
+    /// hasn't caught up yet.
+    pub fn finish(self) -> Result<(), Buffer> {
+        let buffer = self.inner.finish().freeze();
+        // SAFETY: `Sender` is the single producer of `queue`.
+        unsafe { self.queue.push(buffer) }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> Write for FrameWriter<'a> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
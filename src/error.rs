@@ -0,0 +1,30 @@
+use core::fmt;
+
+/// Errors produced by the ring allocator's own bookkeeping. Kept
+/// `core`-only so the allocator and [`crate::BufferWriter`] compile
+/// without `std`; under the `std` feature it converts into
+/// [`std::io::Error`] for the `Write` impl.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// No contiguous span of `available` headers was large enough to
+    /// satisfy the request.
+    Full,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Full => f.write_str("ring buffer is full"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+#[cfg(feature = "std")]
+impl From<Error> for std::io::Error {
+    fn from(err: Error) -> Self {
+        std::io::Error::other(err)
+    }
+}
@@ -1,5 +1,6 @@
-use std::sync::atomic::{AtomicU32, Ordering::*};
+use core::sync::atomic::{AtomicU32, Ordering::*};
 
+#[derive(Debug)]
 pub(crate) struct Header<P>(pub(crate) P);
 
 pub(crate) type HeaderMut = Header<*mut AtomicU32>;
@@ -22,6 +23,19 @@ impl HeaderMut {
         unsafe { &*self.0 }.store(size << 1, Release);
     }
 
+    /// Atomically rewrites this header's capacity, but only if it is still
+    /// `available` with exactly `expected` capacity. Leaves the busy bit
+    /// untouched — marking busy is still the caller's job via `set()`, same
+    /// as every other allocation path — this only settles *which* racing
+    /// claim of this same free header wins. Returns `false` if a concurrent
+    /// allocation already claimed or resized it, in which case the caller
+    /// must rescan rather than trust anything it read about this header.
+    pub(crate) fn try_claim(&self, expected: u32, capacity: u32) -> bool {
+        unsafe { &*self.0 }
+            .compare_exchange(expected << 1, capacity << 1, Release, Relaxed)
+            .is_ok()
+    }
+
     pub(crate) fn capacity(&self) -> u32 {
         unsafe { &*self.0 }.load(Acquire) >> 1
     }
@@ -39,6 +53,10 @@ impl HeaderRo {
     pub(crate) fn unset(&self) {
         unsafe { &*self.0 }.fetch_and(u32::MAX << 1, Release);
     }
+
+    pub(crate) fn capacity(&self) -> u32 {
+        unsafe { &*self.0 }.load(Acquire) >> 1
+    }
 }
 
 impl Clone for HeaderRo {
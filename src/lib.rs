@@ -1,8 +1,32 @@
-use std::{
-    io::{self, Write},
-    ops::{Deref, DerefMut},
-    sync::atomic::{AtomicU32, Ordering::*},
-};
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, vec::Vec};
+
+use core::sync::atomic::{AtomicUsize, Ordering::*};
+
+mod buffer;
+#[cfg(feature = "alloc")]
+mod channel;
+mod error;
+mod header;
+#[cfg(all(test, feature = "std"))]
+mod tests;
+
+use header::{Header, HeaderMut};
+
+#[cfg(feature = "alloc")]
+pub use buffer::Buffer;
+pub use buffer::{BufferMut, BufferWriter};
+#[cfg(feature = "alloc")]
+pub use channel::{channel, FrameWriter, Receiver, Sender};
+pub use error::Error;
 
 const U32SIZE: usize = size_of::<u32>();
 const MAXALLOC: u32 = u32::MAX >> 1;
@@ -13,15 +37,19 @@ const MINALLOCBYTES: usize = MINALLOC as usize * U32SIZE;
 pub struct Ringal {
     start: *mut u32,
     end: *const u32,
-    head: *mut u32,
+    head: AtomicUsize,
 }
 
-type HeaderMut = Header<*mut AtomicU32>;
-type HeaderRo = Header<*const AtomicU32>;
-
-struct Header<P>(P);
+// SAFETY: every mutation of shared ring state goes through `advance`'s
+// CAS on `head`, which is the sole point of contention; a header is only
+// ever handed out to the thread whose CAS publishing it succeeded, so
+// `Ringal` can be freely shared and sent across threads, e.g. behind an
+// `Arc`, without a surrounding `Mutex`.
+unsafe impl Send for Ringal {}
+unsafe impl Sync for Ringal {}
 
 impl Ringal {
+    #[cfg(feature = "alloc")]
     pub fn new(capacity: usize) -> Self {
         assert!(capacity > U32SIZE + MINALLOCBYTES);
         let capacity = capacity.next_power_of_two() / U32SIZE;
@@ -33,19 +61,43 @@ impl Ringal {
         }
 
         let start = Box::leak(buffer.into_boxed_slice()).as_mut_ptr();
-        let head = start;
         let end = unsafe { start.add(capacity - 1) };
-        Self { start, end, head }
+        Self {
+            start,
+            end,
+            head: AtomicUsize::new(0),
+        }
+    }
+
+    /// Initializes the allocator directly over caller-supplied storage, with
+    /// no heap involvement whatsoever — the only constructor available
+    /// without the `alloc` feature, for targets with RAM for the ring but no
+    /// global allocator.
+    pub fn from_backing(store: &'static mut [u32]) -> Self {
+        assert!(store.len() > MINALLOC as usize + 1);
+        let cap = store.len() as u32;
+        for (i, slot) in store.iter_mut().enumerate() {
+            // TODO(perf): figure out how init this memory faster
+            *slot = MAXALLOC.min(cap - i as u32 - 1) << 1;
+        }
+
+        let start = store.as_mut_ptr();
+        let end = unsafe { start.add(store.len() - 1) };
+        Self {
+            start,
+            end,
+            head: AtomicUsize::new(0),
+        }
     }
 
-    fn alloc(&mut self, min: usize) -> Option<HeaderMut> {
+    fn alloc(&self, min: usize) -> Option<HeaderMut> {
         let min = (min / U32SIZE) as u32 + (min % U32SIZE != 0) as u32;
         (MINALLOC..MAXALLOC).contains(&min).then_some(())?;
 
         self.advance(min)
     }
 
-    pub fn writer(&mut self, min: usize) -> Option<BufferWriter<'_>> {
+    pub fn writer(&self, min: usize) -> Option<BufferWriter<'_>> {
         let header = self.alloc(min)?;
         let inner = header.buffer();
         let capacity = header.capacity() as usize * U32SIZE;
@@ -63,341 +115,112 @@ impl Ringal {
     /// returns uninitialized memory of requested size rounded up to alignment
     /// call `fill` on returned buffer if initializition is required, otherwise
     /// don't read data before writing something to buffer
-    pub unsafe fn fixed(&mut self, min: usize) -> Option<BufferMut> {
+    pub unsafe fn fixed(&self, min: usize) -> Option<BufferMut> {
         let header = self.alloc(min)?;
-        let capacity = header.capacity() as usize;
+        let capacity = header.capacity() as usize * U32SIZE;
         let inner = header.buffer();
         header.set();
         let buffer = BufferMut {
             header: header.into(),
-            inner: unsafe { std::slice::from_raw_parts_mut(inner, capacity) },
+            inner: unsafe { core::slice::from_raw_parts_mut(inner, capacity) },
+            len: 0,
         };
         Some(buffer)
     }
 
-    fn extend(&mut self, header: &HeaderMut, extra: usize) -> io::Result<()> {
-        let extra = self
-            .alloc(extra - U32SIZE)
-            .ok_or_else(|| io::Error::other("ring buffer is full"))?;
+    fn extend(&self, header: &HeaderMut, extra: usize) -> Result<(), Error> {
+        let extra = self.alloc(extra - U32SIZE).ok_or(Error::Full)?;
         let capacity = extra.capacity() + 1 + header.capacity();
         header.store(capacity);
         Ok(())
     }
 
-    fn advance(&mut self, capacity: u32) -> Option<HeaderMut> {
-        let mut accumulated = 0;
-        let mut current = self.head;
-        let mut wrapped = false;
+    /// Claims `capacity` worth of free space starting at the current head,
+    /// coalescing adjacent free headers as needed. Concurrent callers that
+    /// read the same stale head land on the same first header, so that
+    /// header's own `try_claim` CAS is what actually serializes them —
+    /// only the winner goes on to publish a new head, everyone else
+    /// rescans — making this lock-free without a surrounding mutex.
+    fn advance(&self, capacity: u32) -> Option<HeaderMut> {
         loop {
-            let header = Header::new(current);
-            header.available().then_some(())?;
-
-            let size = header.capacity();
-            accumulated += size;
-            if accumulated >= capacity {
-                break;
+            let offset = self.head.load(Acquire);
+            let head = unsafe { self.start.add(offset) };
+
+            let mut accumulated = 0;
+            let mut current = head;
+            let mut claimed = head;
+            let mut claimed_size = 0;
+            let mut leg_start = true;
+            loop {
+                let header = Header::new(current);
+                header.available().then_some(())?;
+
+                let size = header.capacity();
+                if leg_start {
+                    claimed = current;
+                    claimed_size = size;
+                    leg_start = false;
+                }
+                accumulated += size;
+                if accumulated >= capacity {
+                    break;
+                }
+
+                let next = unsafe { current.add(size as usize + U32SIZE) };
+                accumulated += 1;
+
+                current = if next.cast_const() >= self.end {
+                    accumulated = 0;
+                    leg_start = true;
+                    self.start
+                } else {
+                    next
+                };
+                (head != current).then_some(())?;
             }
 
-            let next = unsafe { current.add(size as usize + U32SIZE) };
-            accumulated += 1;
-
-            current = if next.cast_const() >= self.end {
-                accumulated = 0;
-                wrapped = true;
-                self.start
+            let header = Header::new(claimed);
+            let cap = if accumulated - capacity <= MINALLOC {
+                accumulated
             } else {
-                next
+                capacity
             };
-            (self.head != current).then_some(())?;
-        }
-        if wrapped {
-            self.head = self.start
-        };
-        let header = Header::new(self.head);
-        let cap = if accumulated - capacity <= MINALLOC {
-            accumulated
-        } else {
-            capacity
-        };
-        header.store(cap);
-        let next = unsafe { self.head.add(cap as usize + 1) };
-        if next.cast_const() >= self.end {
-            self.head = self.start;
-        } else {
-            self.head = next;
-            if accumulated - capacity > MINALLOC {
-                let header = Header::new(self.head);
-                let distance = unsafe { self.end.offset_from(self.head) } as u32;
-                header.store((accumulated - capacity).min(distance));
-                header.unset();
-            }
-        }
-
-        Some(header)
-    }
-}
-
-impl Clone for HeaderRo {
-    fn clone(&self) -> Self {
-        Self(self.0)
-    }
-}
-
-impl HeaderMut {
-    fn new(ptr: *mut u32) -> Self {
-        Self(ptr as *mut AtomicU32)
-    }
-
-    fn set(&self) {
-        unsafe { &*self.0 }.fetch_or(1, Release);
-    }
-
-    fn unset(&self) {
-        unsafe { &*self.0 }.fetch_and(u32::MAX << 1, Release);
-    }
-
-    fn store(&self, size: u32) {
-        unsafe { &*self.0 }.store(size << 1, Release);
-    }
-
-    fn capacity(&self) -> u32 {
-        unsafe { &*self.0 }.load(Acquire) >> 1
-    }
-
-    fn available(&self) -> bool {
-        (unsafe { &*self.0 }.load(Acquire) & 1) == 0
-    }
-
-    fn buffer(&self) -> *mut u8 {
-        unsafe { self.0.add(1) as *mut u8 }
-    }
-}
-
-impl HeaderRo {
-    fn unset(&self) {
-        unsafe { &*self.0 }.fetch_and(u32::MAX << 1, Release);
-    }
-}
 
-pub struct BufferWriter<'a> {
-    inner: *mut u8,
-    header: Header<*mut AtomicU32>,
-    initialized: usize,
-    capacity: usize,
-    ringo: &'a mut Ringal,
-}
-
-impl<'a> BufferWriter<'a> {
-    pub fn finish(self) -> BufferMut {
-        let inner = unsafe { std::slice::from_raw_parts_mut(self.inner, self.initialized) };
-        self.header.set();
-        BufferMut {
-            header: self.header.into(),
-            inner,
-        }
-    }
-}
-
-impl<'a> Write for BufferWriter<'a> {
-    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        if self.initialized == MAXALLOCBYTES {
-            io::Error::other("max allocation size reached");
-        }
-        let len = buf.len().min(MAXALLOCBYTES - self.initialized);
-
-        let required = self.initialized + len;
-        if required > self.capacity {
-            let extra = (required - self.capacity).max(MINALLOCBYTES);
-            self.ringo.extend(&self.header, extra)?;
-            self.capacity = self.header.capacity() as usize * U32SIZE;
-        }
-        unsafe {
-            self.inner
-                .add(self.initialized)
-                .copy_from_nonoverlapping(buf.as_ptr(), len)
-        };
-        self.initialized += len;
-        Ok(len)
-    }
-
-    fn flush(&mut self) -> std::io::Result<()> {
-        Ok(())
-    }
-}
-
-pub struct BufferMut {
-    header: HeaderRo,
-    inner: &'static mut [u8],
-}
-
-pub struct Buffer {
-    header: HeaderRo,
-    inner: &'static [u8],
-    rc: &'static AtomicU32,
-}
-
-impl From<HeaderMut> for HeaderRo {
-    fn from(value: HeaderMut) -> Self {
-        Self(value.0)
-    }
-}
-
-impl Deref for Buffer {
-    type Target = [u8];
-    fn deref(&self) -> &Self::Target {
-        self.inner
-    }
-}
-
-impl Clone for Buffer {
-    fn clone(&self) -> Self {
-        self.rc.fetch_add(1, Release);
-        Self {
-            inner: self.inner,
-            rc: self.rc,
-            header: self.header.clone(),
-        }
-    }
-}
-
-impl Drop for Buffer {
-    fn drop(&mut self) {
-        let mut count = self.rc.load(Acquire);
-        loop {
-            if count == 1 {
-                break;
+            // Claim the header itself before touching `head`: a concurrent
+            // `advance` racing from this same stale snapshot would compute
+            // this exact same `claimed`/`cap`, so only one of them may win
+            // this CAS. Losing it means someone else got here first, so
+            // rescan against whatever the head is now rather than trusting
+            // anything computed above.
+            if !header.try_claim(claimed_size, cap) {
+                continue;
             }
-            if let Err(c) = self.rc.compare_exchange(count, count - 1, Release, Acquire) {
-                count = c;
-            } else {
-                return;
-            }
-        }
-        self.header.unset();
-        // SAFETY: checked above that we are the last reference holder,
-        // which makes it safe to reclaim the storage for AtomicU32
-        let _ = unsafe { Box::from_raw(self.rc.as_ptr() as *mut AtomicU32) };
-    }
-}
-
-impl Drop for BufferMut {
-    fn drop(&mut self) {
-        self.header.unset();
-    }
-}
 
-impl Deref for BufferMut {
-    type Target = [u8];
-    fn deref(&self) -> &Self::Target {
-        self.inner
-    }
-}
-
-impl DerefMut for BufferMut {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        self.inner
-    }
-}
-
-impl BufferMut {
-    pub fn init(&mut self) {
-        self.inner.fill(0);
-    }
-
-    pub fn freeze(self) -> Buffer {
-        let rc = Box::leak(Box::new(AtomicU32::new(1)));
-        let inner = unsafe { std::slice::from_raw_parts(self.inner.as_ptr(), self.inner.len()) };
-        let ro = Buffer {
-            inner,
-            rc,
-            header: self.header.clone(),
-        };
-        // don't run Drop on BufferMut, Buffer is now responsible for cleanup
-        std::mem::forget(self);
-        ro
-    }
-}
-#[cfg(test)]
-mod tests {
-    use std::collections::VecDeque;
-
-    use crate::*;
-    const BUFSIZE: usize = 1024;
-    const CHUNKSIZE: usize = MINALLOCBYTES;
-    const TESTMSG: &[u8] = b"TEST MESSAGE";
-    const LONGMSG: &[u8] = b"THIS IS A VERY LONG MESSAGE! THIS IS A VERY LONG MESSAGE! THIS IS A VERY LONG MESSAGE! THIS IS A VERY LONG MESSAGE! THIS IS A VERY LONG MESSAGE!";
-    const LONGMSGLEN: usize = LONGMSG.len();
-
-    #[test]
-    fn test_alloc() {
-        let mut ringal = Ringal::new(BUFSIZE);
-        let header1 = ringal.alloc(CHUNKSIZE).unwrap();
-        assert!(header1.available());
-        assert_eq!(header1.capacity(), (CHUNKSIZE / U32SIZE) as u32);
-        let header2 = ringal.alloc(CHUNKSIZE).unwrap();
-        assert_eq!(header2.0, unsafe { header1.0.add(CHUNKSIZE / U32SIZE + 1) });
-    }
-
-    #[test]
-    fn test_writer() {
-        let mut ringal = Ringal::new(BUFSIZE);
-
-        let mut buffer = ringal.writer(CHUNKSIZE).unwrap();
-        assert!(buffer.write(TESTMSG).is_ok());
-        let buffer = buffer.finish();
-        assert_eq!(buffer.as_ref(), TESTMSG);
-        let buffer = buffer.freeze();
-        assert_eq!(buffer.as_ref(), TESTMSG);
-    }
-
-    #[test]
-    fn test_extendable_writer() {
-        let mut ringal = Ringal::new(BUFSIZE);
-
-        let mut buffer = ringal.writer(CHUNKSIZE).unwrap();
-        let result = buffer.write(LONGMSG);
-        assert!(result.is_ok());
-        let buffer = buffer.finish();
-        assert_eq!(buffer.as_ref(), LONGMSG);
-        let header = ringal.alloc(CHUNKSIZE).unwrap();
-        let offset = unsafe { buffer.as_ptr().add(LONGMSGLEN) };
-        assert_eq!(offset, header.0 as *const u8);
-    }
-
-    #[test]
-    fn test_alloc_fail() {
-        let mut ringal = Ringal::new(BUFSIZE);
-        let count = BUFSIZE / (CHUNKSIZE + U32SIZE);
-        let mut buffers = Vec::with_capacity(count);
-        for _ in 0..count {
-            let buffer = unsafe { ringal.fixed(CHUNKSIZE) }.unwrap();
-            buffers.push(buffer);
-        }
-        let buffer = ringal.alloc(CHUNKSIZE);
-        assert!(buffer.is_none());
-    }
-
-    #[test]
-    fn test_realloc() {
-        let mut ringal = Ringal::new(BUFSIZE);
-        let buffer1 = unsafe { ringal.fixed(BUFSIZE / 2 - U32SIZE * 2) }.unwrap();
-        let _buffer2 = unsafe { ringal.fixed(BUFSIZE / 2 - U32SIZE * 2) }.unwrap();
-        assert!(ringal.alloc(CHUNKSIZE).is_none());
-        drop(buffer1);
-        assert!(ringal.alloc(CHUNKSIZE).is_some());
-    }
+            let next = unsafe { claimed.add(cap as usize + 1) };
+            let new_offset = if next.cast_const() >= self.end {
+                0
+            } else {
+                if accumulated - capacity > MINALLOC {
+                    let leftover = Header::new(next);
+                    let distance = unsafe { self.end.offset_from(next) } as u32;
+                    leftover.store((accumulated - capacity).min(distance));
+                }
+                unsafe { next.offset_from(self.start) as usize }
+            };
 
-    #[test]
-    fn test_continuous_realloc() {
-        let mut ringal = Ringal::new(BUFSIZE);
-        let iterations = BUFSIZE / (CHUNKSIZE + U32SIZE) * 10;
-        let mut buffers = VecDeque::with_capacity(2);
-        buffers.push_back(unsafe { ringal.fixed(MINALLOCBYTES) }.unwrap());
-        for i in MINALLOCBYTES..MINALLOCBYTES * 2 {
-            for _ in 0..iterations {
-                buffers.push_back(unsafe { ringal.fixed(i) }.unwrap());
-                buffers.pop_front();
+            if self
+                .head
+                .compare_exchange(offset, new_offset, Release, Acquire)
+                .is_ok()
+            {
+                return Some(header);
             }
+
+            // We already won the header's own CAS above, so we're its sole
+            // owner here and it's safe to hand it back with a plain store:
+            // restore its original, unmerged size and retry against the
+            // fresh head.
+            header.store(claimed_size);
         }
     }
 }
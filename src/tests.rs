@@ -1,9 +1,12 @@
 use std::{
     collections::VecDeque,
+    io::{self, Write},
     sync::{Arc, Condvar, Mutex},
 };
 
-use io::Write;
+use alloc::vec;
+
+use bytes::{Buf, BufMut};
 
 use crate::*;
 
@@ -17,7 +20,7 @@ const LONGMSGLEN: usize = LONGMSG.len();
 
 #[test]
 fn test_alloc() {
-    let mut ringal = Ringal::new(BUFSIZE);
+    let ringal = Ringal::new(BUFSIZE);
     let header1 = ringal.alloc(CHUNKSIZE).unwrap();
     assert!(header1.available());
     assert_eq!(header1.capacity(), (CHUNKSIZE / U32SIZE) as u32);
@@ -27,7 +30,7 @@ fn test_alloc() {
 
 #[test]
 fn test_writer() {
-    let mut ringal = Ringal::new(BUFSIZE);
+    let ringal = Ringal::new(BUFSIZE);
 
     let mut buffer = ringal.writer(CHUNKSIZE).unwrap();
     assert!(buffer.write(TESTMSG).is_ok());
@@ -39,7 +42,7 @@ fn test_writer() {
 
 #[test]
 fn test_extendable_writer() {
-    let mut ringal = Ringal::new(BUFSIZE);
+    let ringal = Ringal::new(BUFSIZE);
 
     let mut buffer = ringal.writer(CHUNKSIZE).unwrap();
     let result = buffer.write(LONGMSG);
@@ -53,11 +56,11 @@ fn test_extendable_writer() {
 
 #[test]
 fn test_alloc_fail() {
-    let mut ringal = Ringal::new(BUFSIZE);
+    let ringal = Ringal::new(BUFSIZE);
     let count = BUFSIZE / (CHUNKSIZE + U32SIZE);
     let mut buffers = Vec::with_capacity(count);
     for _ in 0..count {
-        let buffer = ringal.fixed(CHUNKSIZE).unwrap();
+        let buffer = unsafe { ringal.fixed(CHUNKSIZE) }.unwrap();
         buffers.push(buffer);
     }
     let buffer = ringal.alloc(CHUNKSIZE);
@@ -66,9 +69,9 @@ fn test_alloc_fail() {
 
 #[test]
 fn test_realloc() {
-    let mut ringal = Ringal::new(BUFSIZE);
-    let buffer1 = ringal.fixed(BUFSIZE / 2 - U32SIZE * 2).unwrap();
-    let _buffer2 = ringal.fixed(BUFSIZE / 2 - U32SIZE * 2).unwrap();
+    let ringal = Ringal::new(BUFSIZE);
+    let buffer1 = unsafe { ringal.fixed(BUFSIZE / 2 - U32SIZE * 2) }.unwrap();
+    let _buffer2 = unsafe { ringal.fixed(BUFSIZE / 2 - U32SIZE * 2) }.unwrap();
     assert!(ringal.alloc(CHUNKSIZE).is_none());
     drop(buffer1);
     assert!(ringal.alloc(CHUNKSIZE).is_some());
@@ -76,13 +79,13 @@ fn test_realloc() {
 
 #[test]
 fn test_continuous_realloc() {
-    let mut ringal = Ringal::new(BUFSIZE);
+    let ringal = Ringal::new(BUFSIZE);
     let iterations = BUFSIZE / (CHUNKSIZE + U32SIZE) * 10;
     let mut buffers = VecDeque::with_capacity(2);
-    buffers.push_back(ringal.fixed(MINALLOCBYTES).unwrap());
+    buffers.push_back(unsafe { ringal.fixed(MINALLOCBYTES) }.unwrap());
     for i in MINALLOCBYTES..MINALLOCBYTES * 2 {
         for _ in 0..iterations {
-            buffers.push_back(ringal.fixed(i).unwrap());
+            buffers.push_back(unsafe { ringal.fixed(i) }.unwrap());
             buffers.pop_front();
         }
     }
@@ -90,27 +93,27 @@ fn test_continuous_realloc() {
 
 #[test]
 fn test_buffer_drop() {
-    let mut ringal = Ringal::new(BUFSIZE);
-    let mut buffer = ringal.fixed(TESTMSG.len()).unwrap();
+    let ringal = Ringal::new(BUFSIZE);
+    let mut buffer = unsafe { ringal.fixed(TESTMSG.len()) }.unwrap();
     buffer.copy_from_slice(TESTMSG);
     assert_eq!(buffer.as_ref(), TESTMSG);
-    assert!(ringal.fixed(BUFSIZE - TESTMSG.len()).is_none());
+    assert!(unsafe { ringal.fixed(BUFSIZE - TESTMSG.len()) }.is_none());
     drop(buffer);
-    let buffer = ringal.fixed(BUFSIZE - TESTMSG.len()).unwrap();
+    let buffer = unsafe { ringal.fixed(BUFSIZE - TESTMSG.len()) }.unwrap();
     let buffer = buffer.freeze();
-    assert!(ringal.fixed(BUFSIZE - TESTMSG.len()).is_none());
+    assert!(unsafe { ringal.fixed(BUFSIZE - TESTMSG.len()) }.is_none());
     drop(buffer);
-    assert!(ringal.fixed(BUFSIZE - TESTMSG.len()).is_some());
+    assert!(unsafe { ringal.fixed(BUFSIZE - TESTMSG.len()) }.is_some());
 }
 
 #[test]
 fn test_multithreaded_drops() {
-    let mut ringal = Ringal::new(BUFSIZE);
+    let ringal = Ringal::new(BUFSIZE);
     let iterations = BUFSIZE / (CHUNKSIZE + U32SIZE);
     let mut handles = Vec::with_capacity(iterations);
     let signal = Arc::new((Mutex::new(0), Condvar::new()));
     for _ in 0..iterations {
-        let mut buffer = ringal.fixed(CHUNKSIZE).unwrap();
+        let mut buffer = unsafe { ringal.fixed(CHUNKSIZE) }.unwrap();
         let signal = signal.clone();
         let handle = std::thread::spawn(move || {
             buffer.copy_from_slice(TESTMSG);
@@ -126,18 +129,18 @@ fn test_multithreaded_drops() {
             break;
         }
     }
-    assert!(ringal.fixed(BUFSIZE - TESTMSG.len()).is_none());
+    assert!(unsafe { ringal.fixed(BUFSIZE - TESTMSG.len()) }.is_none());
     signal.1.notify_all();
     for h in handles {
         assert!(h.join().is_ok());
     }
-    assert!(ringal.fixed(BUFSIZE / TESTMSG.len()).is_some());
+    assert!(unsafe { ringal.fixed(BUFSIZE / TESTMSG.len()) }.is_some());
 }
 
 #[test]
 fn test_multithreaded_buffer_clones() {
-    let mut ringal = Ringal::new(BUFSIZE);
-    let mut buffer = ringal.fixed(TESTMSG.len()).unwrap();
+    let ringal = Ringal::new(BUFSIZE);
+    let mut buffer = unsafe { ringal.fixed(TESTMSG.len()) }.unwrap();
     buffer.copy_from_slice(TESTMSG);
     let buffer = buffer.freeze();
     let clones = 64;
@@ -159,10 +162,210 @@ fn test_multithreaded_buffer_clones() {
             break;
         }
     }
-    assert!(ringal.fixed(BUFSIZE - TESTMSG.len()).is_none());
+    assert!(unsafe { ringal.fixed(BUFSIZE - TESTMSG.len()) }.is_none());
     signal.1.notify_all();
     for h in handles {
         assert!(h.join().is_ok());
     }
-    assert!(ringal.fixed(BUFSIZE / TESTMSG.len()).is_some());
+    assert!(unsafe { ringal.fixed(BUFSIZE / TESTMSG.len()) }.is_some());
+}
+
+#[test]
+fn test_buffer_split_and_buf() {
+    let ringal = Ringal::new(BUFSIZE);
+    let mut writer = ringal.writer(CHUNKSIZE).unwrap();
+    assert!(writer.write(LONGMSG).is_ok());
+    let mut buffer = writer.finish().freeze();
+
+    buffer.advance(20);
+    assert_eq!(buffer.remaining(), LONGMSGLEN - 20);
+    assert_eq!(buffer.chunk(), &LONGMSG[20..]);
+
+    // split_off(10) splits at an absolute index the cursor has already
+    // advanced past; `self`'s cursor must clamp to the new, shorter
+    // `inner` instead of drifting past its end.
+    let tail = buffer.split_off(10);
+    assert_eq!(buffer.remaining(), 0);
+    assert_eq!(buffer.as_ref(), &LONGMSG[..10]);
+    assert_eq!(tail.as_ref(), &LONGMSG[10..]);
+
+    let mut tail = tail;
+    let front = tail.split_to(5);
+    assert_eq!(front.as_ref(), &LONGMSG[10..15]);
+    assert_eq!(tail.as_ref(), &LONGMSG[15..]);
+}
+
+#[test]
+fn test_buffer_try_into_mut_and_make_mut() {
+    let ringal = Ringal::new(BUFSIZE);
+
+    // sole owner: try_into_mut succeeds and hands back the very same memory
+    let mut buffer = unsafe { ringal.fixed(TESTMSG.len()) }.unwrap();
+    buffer.copy_from_slice(TESTMSG);
+    let ptr = buffer.as_ptr();
+    let buffer = buffer.freeze();
+    let mut mutable = buffer.try_into_mut().unwrap();
+    assert_eq!(mutable.as_ptr(), ptr);
+    mutable[0] = b'X';
+
+    let mut buffer = mutable.freeze();
+    // shared: try_into_mut hands the Buffer back unchanged
+    let clone = buffer.clone();
+    buffer = buffer.try_into_mut().unwrap_err();
+    assert_eq!(buffer.as_ref(), clone.as_ref());
+    drop(clone);
+
+    // make_mut on a shared buffer copies into a fresh frame and leaves the
+    // original shared copy alive
+    let mut shared = buffer.clone();
+    let copy = shared.make_mut(&ringal);
+    assert_eq!(copy.as_ref(), buffer.as_ref());
+    assert_ne!(copy.as_ptr(), buffer.as_ptr());
+    drop(copy);
+    drop(shared);
+
+    // make_mut on a sole-owned buffer promotes in place over the same memory
+    let ptr = buffer.as_ptr();
+    let promoted = buffer.make_mut(&ringal);
+    assert_eq!(promoted.as_ptr(), ptr);
+}
+
+#[test]
+fn test_concurrent_alloc() {
+    let ringal = Ringal::new(BUFSIZE);
+    let ranges = Mutex::new(Vec::new());
+    let threads = 8;
+    let per_thread = 4;
+
+    std::thread::scope(|scope| {
+        for _ in 0..threads {
+            let ringal = &ringal;
+            let ranges = &ranges;
+            scope.spawn(move || {
+                for _ in 0..per_thread {
+                    if let Some(buffer) = unsafe { ringal.fixed(CHUNKSIZE) } {
+                        let start = buffer.as_ptr() as usize;
+                        let end = start + buffer.len();
+                        ranges.lock().unwrap().push((start, end));
+                        // leak on purpose: we only care that no two threads
+                        // were ever handed overlapping memory, not about
+                        // releasing it back before the ring runs out.
+                        core::mem::forget(buffer);
+                    }
+                }
+            });
+        }
+    });
+
+    let ranges = ranges.into_inner().unwrap();
+    for (i, &(s1, e1)) in ranges.iter().enumerate() {
+        for &(s2, e2) in ranges.iter().skip(i + 1) {
+            assert!(
+                e1 <= s2 || e2 <= s1,
+                "concurrent allocations handed out overlapping memory"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_buffer_mut_resize_and_spare_capacity() {
+    let ringal = Ringal::new(BUFSIZE);
+    let mut buffer = unsafe { ringal.fixed(CHUNKSIZE) }.unwrap();
+    buffer.put_slice(TESTMSG);
+
+    let limits = buffer.limits();
+    assert_eq!(limits.len, TESTMSG.len());
+    assert_eq!(limits.capacity, CHUNKSIZE);
+
+    // a grow of a couple bytes used to underflow inside `Ringal::extend`
+    // because `resize` passed the raw, unfloored delta straight through
+    buffer.resize(&ringal, limits.len + 2).unwrap();
+    assert_eq!(buffer.len(), limits.len + 2);
+    assert_eq!(&buffer[..TESTMSG.len()], TESTMSG);
+    assert_eq!(&buffer[TESTMSG.len()..], &[0, 0]);
+
+    // a grow past the current ring capacity extends the backing allocation
+    let grown = buffer.limits().capacity + MINALLOCBYTES;
+    buffer.resize(&ringal, grown).unwrap();
+    let limits = buffer.limits();
+    assert_eq!(limits.len, grown);
+    assert!(limits.capacity >= grown);
+
+    let spare = buffer.spare_capacity_mut();
+    assert_eq!(spare.len(), limits.capacity - limits.len);
+
+    // shrinking just narrows the initialized view, no reallocation needed
+    buffer.resize(&ringal, TESTMSG.len()).unwrap();
+    assert_eq!(buffer.as_ref(), TESTMSG);
+}
+
+#[test]
+fn test_channel_send_recv() {
+    let (mut sender, mut receiver) = channel(BUFSIZE);
+
+    let mut writer = sender.writer(CHUNKSIZE).unwrap();
+    assert!(writer.write(TESTMSG).is_ok());
+    assert!(writer.finish().is_ok());
+
+    let frame = receiver.recv().unwrap();
+    assert_eq!(frame.as_ref(), TESTMSG);
+    assert!(receiver.recv().is_none());
+}
+
+#[test]
+fn test_channel_backpressure_and_teardown_with_pending_frames() {
+    let depth = BUFSIZE / MINALLOCBYTES;
+    let (mut sender, receiver) = channel(BUFSIZE);
+
+    let mut sent = 0;
+    loop {
+        let Some(mut writer) = sender.writer(CHUNKSIZE) else {
+            break;
+        };
+        assert!(writer.write(TESTMSG).is_ok());
+        if writer.finish().is_err() {
+            break;
+        }
+        sent += 1;
+    }
+    assert!(sent > 0 && sent <= depth);
+
+    // tearing the channel down with unconsumed frames still queued must not
+    // panic: `Queue`'s `Drop` has to run each pending `Buffer`'s destructor
+    // itself, since `MaybeUninit` won't do it for them.
+    drop(sender);
+    drop(receiver);
+}
+
+#[test]
+fn test_writer_read_from_and_write_vectored() {
+    let ringal = Ringal::new(BUFSIZE);
+
+    let mut writer = ringal.writer(CHUNKSIZE).unwrap();
+    let mut src = &TESTMSG[..16];
+    let read = writer.read_from(&mut src, 16).unwrap();
+    assert_eq!(read, 16);
+
+    let parts = [
+        io::IoSlice::new(&TESTMSG[16..24]),
+        io::IoSlice::new(&TESTMSG[24..]),
+    ];
+    let written = writer.write_vectored(&parts).unwrap();
+    assert_eq!(written, TESTMSG.len() - 16);
+
+    let buffer = writer.finish().freeze();
+    assert_eq!(buffer.as_ref(), TESTMSG);
+}
+
+#[test]
+fn test_from_backing() {
+    let store = vec![0u32; BUFSIZE / U32SIZE].into_boxed_slice();
+    let store: &'static mut [u32] = Box::leak(store);
+    let ringal = Ringal::from_backing(store);
+
+    let mut writer = ringal.writer(CHUNKSIZE).unwrap();
+    assert!(writer.write(TESTMSG).is_ok());
+    let buffer = writer.finish().freeze();
+    assert_eq!(buffer.as_ref(), TESTMSG);
 }